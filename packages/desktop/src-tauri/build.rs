@@ -0,0 +1,10 @@
+fn main() {
+    tauri_build::build();
+
+    // Bake the compile-time target triple into the binary so commands can
+    // report it without re-deriving it from std::env::consts alone.
+    println!(
+        "cargo:rustc-env=TARGET_TRIPLE={}",
+        std::env::var("TARGET").expect("TARGET is set by cargo for build scripts")
+    );
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 
 #[tauri::command]
 pub fn get_app_version() -> String {
@@ -8,8 +11,132 @@ pub fn get_app_version() -> String {
 
 #[tauri::command]
 pub fn get_data_dir(app: AppHandle) -> Result<String, String> {
-    app.path()
-        .app_data_dir()
-        .map(|p: PathBuf| p.to_string_lossy().to_string())
-        .map_err(|e: tauri::Error| e.to_string())
+    crate::config::default_data_dir(&app).map(|p| p.to_string_lossy().to_string())
+}
+
+/// Like `get_data_dir`, but resolves to the user's configured override (set
+/// via `set_data_dir`) when one is present, falling back to the default.
+#[tauri::command]
+pub fn get_effective_data_dir(app: AppHandle) -> Result<String, String> {
+    crate::config::effective_data_dir(&app).map(|p| p.to_string_lossy().to_string())
+}
+
+/// Relocates where the app stores its data, migrating any existing contents
+/// from the current location to `path`. The move is staged so a failure
+/// partway through leaves the original data untouched.
+#[tauri::command]
+pub fn set_data_dir(app: AppHandle, path: String) -> Result<(), String> {
+    crate::config::relocate_data_dir(&app, &PathBuf::from(path))
+}
+
+/// Where to fetch the release manifest that `check_for_update` compares against.
+const UPDATE_MANIFEST_URL: &str = "https://openwork.app/releases/latest.json";
+
+/// How long to wait on the update manifest fetch before giving up, so an
+/// unresponsive update server can't hang `check_for_update` indefinitely.
+const UPDATE_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A single platform's downloadable artifact for a release.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReleasePlatform {
+    pub url: String,
+    pub signature: String,
+}
+
+/// The release manifest as published by the update server.
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseManifest {
+    version: semver::Version,
+    notes: Option<String>,
+    pub_date: Option<String>,
+    platforms: HashMap<String, ReleasePlatform>,
+}
+
+/// A remote release, narrowed to the platform the app is currently running on.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteRelease {
+    pub version: semver::Version,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+    pub platform: ReleasePlatform,
+}
+
+/// Checks the update manifest for a release newer than the running version.
+///
+/// Returns `Ok(None)` when the app is already up to date, and `Ok(Some(..))`
+/// with the release info (including the download for the current target
+/// triple) when an update is available. Manifests that fail to parse, or
+/// that offer a newer version without an entry for the current target
+/// triple, are errors rather than treated as "no update".
+#[tauri::command]
+pub async fn check_for_update() -> Result<Option<RemoteRelease>, String> {
+    let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))
+        .map_err(|e| format!("failed to parse current app version: {e}"))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(UPDATE_FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| format!("failed to build update client: {e}"))?;
+
+    let manifest: ReleaseManifest = client
+        .get(UPDATE_MANIFEST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("failed to fetch update manifest: {e}"))?
+        .json()
+        .await
+        .map_err(|e| format!("failed to parse update manifest: {e}"))?;
+
+    if manifest.version <= current {
+        return Ok(None);
+    }
+
+    let target_triple = TARGET_TRIPLE;
+    let platform = manifest
+        .platforms
+        .get(target_triple)
+        .cloned()
+        .ok_or_else(|| format!("no update artifact published for target '{target_triple}'"))?;
+
+    Ok(Some(RemoteRelease {
+        version: manifest.version,
+        notes: manifest.notes,
+        pub_date: manifest.pub_date,
+        platform,
+    }))
+}
+
+/// Compile-time target triple baked in by `build.rs`, reflecting the triple
+/// this binary was actually built for (not the host running `cargo`).
+const TARGET_TRIPLE: &str = env!("TARGET_TRIPLE");
+
+/// Structured platform metadata, mirroring `get_app_version`/`get_data_dir`
+/// so frontends can show diagnostics or pick an update artifact without
+/// shelling out to `uname`/`ver`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformInfo {
+    pub platform: &'static str,
+    pub family: &'static str,
+    pub arch: &'static str,
+    pub target_triple: &'static str,
+}
+
+#[tauri::command]
+pub fn get_platform_info() -> PlatformInfo {
+    PlatformInfo {
+        platform: platform_name(),
+        family: std::env::consts::FAMILY,
+        arch: std::env::consts::ARCH,
+        target_triple: TARGET_TRIPLE,
+    }
+}
+
+/// `std::env::consts::OS` reports `"macos"`, but the Tauri updater (and
+/// update manifests generally) name that platform `"darwin"`; map it so
+/// frontends gating on the conventional name don't silently miss macOS.
+fn platform_name() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    }
 }
@@ -0,0 +1,314 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Persisted app-level settings, stored alongside (but separate from) the
+/// user's data itself so it survives a data dir relocation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    /// User-chosen override for where app data lives, if any. When absent
+    /// or no longer valid, the Tauri default `app_data_dir` is used.
+    pub data_dir_override: Option<PathBuf>,
+}
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("failed to resolve app config dir: {e}"))?;
+    Ok(dir.join(CONFIG_FILE_NAME))
+}
+
+pub fn load_config(app: &AppHandle) -> Result<AppConfig, String> {
+    let path = config_path(app)?;
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+    let raw = fs::read_to_string(&path).map_err(|e| format!("failed to read {path:?}: {e}"))?;
+    serde_json::from_str(&raw).map_err(|e| format!("failed to parse {path:?}: {e}"))
+}
+
+pub fn save_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    let path = config_path(app)?;
+    let dir = path
+        .parent()
+        .ok_or_else(|| "app config path has no parent directory".to_string())?;
+    fs::create_dir_all(dir).map_err(|e| format!("failed to create {dir:?}: {e}"))?;
+
+    let raw = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+
+    // Write to a sibling temp file and rename into place so a crash or
+    // power loss mid-write can never leave a truncated config.json behind.
+    let tmp_path = dir.join(format!("{CONFIG_FILE_NAME}.tmp"));
+    fs::write(&tmp_path, raw).map_err(|e| format!("failed to write {tmp_path:?}: {e}"))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("failed to replace {path:?}: {e}"))
+}
+
+/// Resolves the data directory the app should actually use right now: the
+/// configured override if one is set and still usable, otherwise the
+/// default `app_data_dir` for this launch.
+pub fn effective_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let config = load_config(app)?;
+    if let Some(override_dir) = valid_override(config.data_dir_override) {
+        return Ok(override_dir);
+    }
+    default_data_dir(app)
+}
+
+/// Returns `override_dir` only if it's still a usable directory (e.g. not
+/// an unplugged external drive or a path that was since deleted).
+fn valid_override(override_dir: Option<PathBuf>) -> Option<PathBuf> {
+    override_dir.filter(|dir| dir.is_dir())
+}
+
+/// The default data dir for this launch, before any user override.
+pub fn default_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("failed to resolve default app data dir: {e}"))
+}
+
+/// Relocates the app's data directory to `new_dir`, moving any existing
+/// contents over and persisting the override so future launches use it.
+///
+/// The copy is staged into a temp directory next to `new_dir` and only
+/// swapped into place once it fully succeeds, so a failure partway through
+/// (e.g. the disk fills up) leaves the original data untouched. Only once
+/// the swap (and the config update pointing at the new location) has
+/// succeeded is the old directory removed, so the app never ends up
+/// permanently duplicating data across both locations.
+pub fn relocate_data_dir(app: &AppHandle, new_dir: &Path) -> Result<(), String> {
+    let old_dir = effective_data_dir(app)?;
+
+    if same_dir(&old_dir, new_dir)? {
+        return Ok(());
+    }
+    reject_if_nested(&old_dir, new_dir)?;
+    stage_copy_and_swap(&old_dir, new_dir)?;
+
+    let mut config = load_config(app)?;
+    config.data_dir_override = Some(new_dir.to_path_buf());
+    save_config(app, &config)?;
+
+    remove_if_exists(&old_dir)
+}
+
+/// True if `old_dir` and `new_dir` resolve to the same location, so a
+/// relocation onto itself is a no-op rather than an error.
+fn same_dir(old_dir: &Path, new_dir: &Path) -> Result<bool, String> {
+    Ok(resolve_for_comparison(old_dir)? == resolve_for_comparison(new_dir)?)
+}
+
+/// Rejects a relocation where `new_dir` is nested inside `old_dir` or vice
+/// versa, comparing resolved (absolute, symlink-free) paths rather than the
+/// raw inputs: a relative `new_dir`, or one reached through a symlink, can
+/// still land inside `old_dir` even though the raw `PathBuf`s don't look
+/// nested, which would send `copy_dir_recursive` into copying a directory
+/// into itself.
+fn reject_if_nested(old_dir: &Path, new_dir: &Path) -> Result<(), String> {
+    let resolved_old = resolve_for_comparison(old_dir)?;
+    let resolved_new = resolve_for_comparison(new_dir)?;
+    if resolved_new.starts_with(&resolved_old) || resolved_old.starts_with(&resolved_new) {
+        return Err("new data dir cannot be nested inside the current data dir, or vice versa".to_string());
+    }
+    Ok(())
+}
+
+/// Stages a copy of `old_dir` (if it exists) into a temp directory next to
+/// `new_dir`, then atomically renames it into place. A failure partway
+/// through the copy, or the final rename, removes the staging directory so
+/// `old_dir` is left untouched and `new_dir` is never left half-populated.
+fn stage_copy_and_swap(old_dir: &Path, new_dir: &Path) -> Result<(), String> {
+    let parent = new_dir
+        .parent()
+        .ok_or_else(|| "new data dir has no parent directory".to_string())?;
+    fs::create_dir_all(parent).map_err(|e| format!("failed to create {parent:?}: {e}"))?;
+
+    if !old_dir.exists() {
+        return fs::create_dir_all(new_dir).map_err(|e| format!("failed to create {new_dir:?}: {e}"));
+    }
+
+    let staging = parent.join(format!(
+        ".openwork-data-migrate-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| e.to_string())?
+            .as_nanos()
+    ));
+
+    if let Err(e) = copy_dir_recursive(old_dir, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("failed to migrate existing data: {e}"));
+    }
+
+    if let Err(e) = fs::rename(&staging, new_dir) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("failed to move migrated data into place: {e}"));
+    }
+
+    Ok(())
+}
+
+fn remove_if_exists(dir: &Path) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    fs::remove_dir_all(dir)
+        .map_err(|e| format!("data was migrated, but removing the old copy at {dir:?} failed: {e}"))
+}
+
+/// Resolves `path` to an absolute, symlink-free form suitable for nesting
+/// comparisons, without requiring `path` itself to exist yet: it walks up
+/// to the nearest existing ancestor, canonicalizes that, then reattaches
+/// the non-existent suffix.
+fn resolve_for_comparison(path: &Path) -> Result<PathBuf, String> {
+    let mut existing = path;
+    let mut suffix = Vec::new();
+    while !existing.exists() {
+        match existing.parent() {
+            Some(parent) => {
+                if let Some(name) = existing.file_name() {
+                    suffix.push(name.to_os_string());
+                }
+                existing = parent;
+            }
+            None => break,
+        }
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("failed to resolve {path:?}: {e}"))?;
+    for name in suffix.into_iter().rev() {
+        resolved.push(name);
+    }
+    Ok(resolved)
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let dst_path = dst.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else {
+            fs::copy(entry.path(), &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn unique_temp_dir(name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!(
+            "openwork-config-test-{name}-{}-{n}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn valid_override_rejects_missing_dir() {
+        let base = unique_temp_dir("valid-override");
+        let real_dir = base.join("real");
+        fs::create_dir_all(&real_dir).unwrap();
+        let missing_dir = base.join("gone");
+
+        assert_eq!(valid_override(Some(real_dir.clone())), Some(real_dir));
+        assert_eq!(valid_override(Some(missing_dir)), None);
+        assert_eq!(valid_override(None), None);
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn reject_if_nested_rejects_target_inside_current_and_vice_versa() {
+        let base = unique_temp_dir("nesting");
+        let old_dir = base.join("data");
+        let nested_new = old_dir.join("sub");
+        fs::create_dir_all(&nested_new).unwrap();
+
+        assert!(reject_if_nested(&old_dir, &nested_new).is_err());
+        assert!(reject_if_nested(&nested_new, &old_dir).is_err());
+
+        let sibling_new = base.join("elsewhere");
+        assert!(reject_if_nested(&old_dir, &sibling_new).is_ok());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn reject_if_nested_follows_symlinks() {
+        let base = unique_temp_dir("nesting-symlink");
+        let real_dir = base.join("real");
+        fs::create_dir_all(&real_dir).unwrap();
+        let link_dir = base.join("link");
+        std::os::unix::fs::symlink(&real_dir, &link_dir).unwrap();
+
+        // `link/sub` resolves through the symlink into `real`, which is
+        // `old_dir` itself, so it must be rejected even though the raw
+        // paths don't share a prefix.
+        let new_via_symlink = link_dir.join("sub");
+        assert!(reject_if_nested(&real_dir, &new_via_symlink).is_err());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn stage_copy_and_swap_moves_contents_into_new_dir() {
+        let base = unique_temp_dir("swap-success");
+        let old_dir = base.join("old");
+        fs::create_dir_all(old_dir.join("sub")).unwrap();
+        fs::write(old_dir.join("file.txt"), b"hello").unwrap();
+        fs::write(old_dir.join("sub/nested.txt"), b"world").unwrap();
+        let new_dir = base.join("new");
+
+        stage_copy_and_swap(&old_dir, &new_dir).unwrap();
+
+        assert_eq!(fs::read(new_dir.join("file.txt")).unwrap(), b"hello");
+        assert_eq!(fs::read(new_dir.join("sub/nested.txt")).unwrap(), b"world");
+        // The swap itself doesn't remove the source; that's the caller's job.
+        assert!(old_dir.join("file.txt").exists());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn stage_copy_and_swap_rolls_back_on_copy_failure() {
+        let base = unique_temp_dir("swap-rollback");
+        let old_dir = base.join("old");
+        fs::create_dir_all(&old_dir).unwrap();
+        fs::write(old_dir.join("file.txt"), b"hello").unwrap();
+        // A symlink to a target that doesn't exist makes `fs::copy` fail
+        // partway through the directory walk.
+        std::os::unix::fs::symlink(base.join("does-not-exist"), old_dir.join("broken-link")).unwrap();
+        let new_dir = base.join("new");
+
+        let result = stage_copy_and_swap(&old_dir, &new_dir);
+
+        assert!(result.is_err());
+        assert!(!new_dir.exists());
+        assert!(old_dir.join("file.txt").exists(), "source must be left untouched");
+        let leftover_staging = fs::read_dir(&base)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .any(|e| e.file_name().to_string_lossy().starts_with(".openwork-data-migrate-"));
+        assert!(!leftover_staging, "failed copy must not leave a staging dir behind");
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -0,0 +1,19 @@
+// Prevents an additional console window on Windows in release builds.
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+mod commands;
+mod config;
+
+fn main() {
+    tauri::Builder::default()
+        .invoke_handler(tauri::generate_handler![
+            commands::get_app_version,
+            commands::get_platform_info,
+            commands::check_for_update,
+            commands::get_data_dir,
+            commands::get_effective_data_dir,
+            commands::set_data_dir,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}